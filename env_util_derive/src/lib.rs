@@ -0,0 +1,210 @@
+//! `#[derive(FromEnv)]`: populates a struct from prefixed environment
+//! variables using the `env_util` `Raw`/`Valid`/`Parsed` pipeline, reporting
+//! every bad field at once via `env_util::Errors` instead of failing on the
+//! first one.
+//!
+//! ```ignore
+//! #[derive(FromEnv)]
+//! #[env(prefix = "APP_")]
+//! struct Config {
+//!     #[env(key = "DATABASE_URL")]
+//!     database_url: String,
+//!     #[env(default = "8080")]
+//!     port: u16,
+//!     #[env(optional)]
+//!     feature_flag: Option<String>,
+//! }
+//! ```
+
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, Data, DeriveInput, Fields, GenericArgument, Lit, Meta, NestedMeta,
+    PathArguments, Type,
+};
+
+#[proc_macro_derive(FromEnv, attributes(env))]
+pub fn derive_from_env(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let ident = &input.ident;
+    let prefix = struct_prefix(&input)?;
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input,
+                    "FromEnv only supports structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input,
+                "FromEnv only supports structs",
+            ))
+        }
+    };
+
+    let mut bindings = Vec::new();
+    let mut field_inits = Vec::new();
+
+    for field in fields {
+        let field_ident = field.ident.as_ref().unwrap();
+        let binding = format_ident!("__{}", field_ident);
+        let attrs = FieldAttrs::parse(field)?;
+        let key = attrs
+            .key
+            .unwrap_or_else(|| format!("{}{}", prefix, field_ident.to_string().to_uppercase()));
+
+        let binding_stmt = if attrs.optional {
+            let inner_ty = option_inner_type(&field.ty).ok_or_else(|| {
+                syn::Error::new_spanned(&field.ty, "#[env(optional)] requires an Option<T> field")
+            })?;
+            quote! {
+                let #binding = match ::env_util::get(#key).optional_checked() {
+                    Ok(Some(valid)) => match valid.then_try_fromstr_into::<#inner_ty>() {
+                        Ok(parsed) => Some(Some(parsed.into_inner())),
+                        Err(err) => {
+                            __errors.push(err);
+                            None
+                        }
+                    },
+                    Ok(None) => Some(None),
+                    Err(err) => {
+                        __errors.push(err);
+                        None
+                    }
+                };
+            }
+        } else {
+            let ty = &field.ty;
+            let raw = match &attrs.default {
+                Some(default) => quote! { ::env_util::get(#key).with_default_checked(#default) },
+                None => quote! { ::env_util::get(#key).required_checked() },
+            };
+            quote! {
+                let #binding = match #raw.and_then(|valid| valid.then_try_fromstr_into::<#ty>()) {
+                    Ok(parsed) => Some(parsed.into_inner()),
+                    Err(err) => {
+                        __errors.push(err);
+                        None
+                    }
+                };
+            }
+        };
+
+        bindings.push(binding_stmt);
+        field_inits.push(quote! { #field_ident: #binding.unwrap() });
+    }
+
+    Ok(quote! {
+        impl #ident {
+            pub fn from_env() -> ::std::result::Result<Self, ::env_util::Errors> {
+                let mut __errors: ::std::vec::Vec<::env_util::Error> = ::std::vec::Vec::new();
+                #(#bindings)*
+                if !__errors.is_empty() {
+                    return ::std::result::Result::Err(::env_util::Errors(__errors));
+                }
+                ::std::result::Result::Ok(Self {
+                    #(#field_inits),*
+                })
+            }
+        }
+    })
+}
+
+struct FieldAttrs {
+    key: Option<String>,
+    default: Option<String>,
+    optional: bool,
+}
+
+impl FieldAttrs {
+    fn parse(field: &syn::Field) -> syn::Result<Self> {
+        let mut key = None;
+        let mut default = None;
+        let mut optional = false;
+
+        for meta in env_metas(&field.attrs)? {
+            match meta {
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("key") => {
+                    key = Some(string_lit(&nv.lit)?);
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("default") => {
+                    default = Some(string_lit(&nv.lit)?);
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("optional") => {
+                    optional = true;
+                }
+                other => {
+                    return Err(syn::Error::new_spanned(
+                        other,
+                        "unrecognized #[env(..)] option",
+                    ))
+                }
+            }
+        }
+
+        Ok(Self {
+            key,
+            default,
+            optional,
+        })
+    }
+}
+
+fn struct_prefix(input: &DeriveInput) -> syn::Result<String> {
+    for meta in env_metas(&input.attrs)? {
+        if let NestedMeta::Meta(Meta::NameValue(nv)) = &meta {
+            if nv.path.is_ident("prefix") {
+                return string_lit(&nv.lit);
+            }
+        }
+    }
+    Ok(String::new())
+}
+
+fn env_metas(attrs: &[syn::Attribute]) -> syn::Result<Vec<NestedMeta>> {
+    let mut metas = Vec::new();
+    for attr in attrs {
+        if !attr.path.is_ident("env") {
+            continue;
+        }
+        if let Meta::List(list) = attr.parse_meta()? {
+            metas.extend(list.nested);
+        }
+    }
+    Ok(metas)
+}
+
+fn string_lit(lit: &Lit) -> syn::Result<String> {
+    match lit {
+        Lit::Str(s) => Ok(s.value()),
+        other => Err(syn::Error::new_spanned(other, "expected a string literal")),
+    }
+}
+
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    args.args.iter().find_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    })
+}
@@ -0,0 +1,116 @@
+use crate::env_util::get;
+use crate::error::{Error, Errors};
+
+use std::{error::Error as StdError, str::FromStr};
+
+/// Accumulates environment validation errors across many keys instead of
+/// failing fast on the first one, so a single run can report every
+/// misconfigured variable at once.
+#[derive(Debug, Default)]
+pub struct EnvBatch {
+    errors: Vec<Error>,
+}
+
+impl EnvBatch {
+    pub fn new() -> Self {
+        Self { errors: Vec::new() }
+    }
+
+    pub fn require<T>(&mut self, key: &str) -> Option<T>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: StdError + Send + Sync + 'static,
+    {
+        match get(key)
+            .required_checked()
+            .and_then(|valid| valid.then_try_fromstr_into())
+        {
+            Ok(parsed) => Some(parsed.into_inner()),
+            Err(err) => {
+                self.errors.push(err);
+                None
+            }
+        }
+    }
+
+    pub fn optional<T>(&mut self, key: &str) -> Option<T>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: StdError + Send + Sync + 'static,
+    {
+        match get(key).optional_checked() {
+            Ok(Some(valid)) => match valid.then_try_fromstr_into() {
+                Ok(parsed) => Some(parsed.into_inner()),
+                Err(err) => {
+                    self.errors.push(err);
+                    None
+                }
+            },
+            Ok(None) => None,
+            Err(err) => {
+                self.errors.push(err);
+                None
+            }
+        }
+    }
+
+    pub fn finish(self) -> Result<(), Errors> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Errors(self.errors))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finish_is_ok_when_every_key_is_valid() {
+        std::env::set_var("ENV_UTIL_BATCH_TEST_OK_PORT", "8080");
+        std::env::remove_var("ENV_UTIL_BATCH_TEST_OK_OPTIONAL");
+
+        let mut batch = EnvBatch::new();
+        let port: Option<u16> = batch.require("ENV_UTIL_BATCH_TEST_OK_PORT");
+        let optional: Option<u16> = batch.optional("ENV_UTIL_BATCH_TEST_OK_OPTIONAL");
+
+        assert_eq!(port, Some(8080));
+        assert_eq!(optional, None);
+        assert!(batch.finish().is_ok());
+
+        std::env::remove_var("ENV_UTIL_BATCH_TEST_OK_PORT");
+    }
+
+    #[test]
+    fn finish_collects_missing_and_bad_parse_errors_in_call_order() {
+        std::env::remove_var("ENV_UTIL_BATCH_TEST_ERR_MISSING");
+        std::env::set_var("ENV_UTIL_BATCH_TEST_ERR_PORT", "not-a-port");
+
+        let mut batch = EnvBatch::new();
+        let missing: Option<u16> = batch.require("ENV_UTIL_BATCH_TEST_ERR_MISSING");
+        let bad_parse: Option<u16> = batch.require("ENV_UTIL_BATCH_TEST_ERR_PORT");
+
+        assert_eq!(missing, None);
+        assert_eq!(bad_parse, None);
+
+        let errors = batch.finish().unwrap_err().into_errors();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].key(), "ENV_UTIL_BATCH_TEST_ERR_MISSING");
+        assert_eq!(errors[1].key(), "ENV_UTIL_BATCH_TEST_ERR_PORT");
+
+        std::env::remove_var("ENV_UTIL_BATCH_TEST_ERR_PORT");
+    }
+
+    #[test]
+    fn optional_is_none_without_error_when_the_var_is_absent() {
+        std::env::remove_var("ENV_UTIL_BATCH_TEST_ABSENT_OPTIONAL");
+
+        let mut batch = EnvBatch::new();
+        let optional: Option<u16> = batch.optional("ENV_UTIL_BATCH_TEST_ABSENT_OPTIONAL");
+
+        assert_eq!(optional, None);
+        assert!(batch.finish().is_ok());
+    }
+}
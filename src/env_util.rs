@@ -1,11 +1,42 @@
-use crate::error::{Error, InvalidUnicodeError, MissingError, ParseError};
+use crate::error::{
+    Error, InvalidUnicodeError, MissingError, OutOfRangeError, ParseError, ParseErrorElement,
+};
+use crate::source::{Env, Source};
 
-use std::{any::type_name, env::var_os, error::Error as StdError, ffi::OsString, str::FromStr};
+use std::{
+    any::type_name,
+    error::Error as StdError,
+    ffi::OsString,
+    fmt,
+    fmt::Display,
+    iter::FromIterator,
+    ops::{Bound, RangeBounds},
+    str::FromStr,
+    time::Duration,
+};
 
-pub fn get(key: &str) -> Raw {
+pub fn get(key: &str) -> Raw<'_> {
+    get_from(&Env, key)
+}
+
+/// Like [`get`], but reads `key` from `source` instead of the live process
+/// environment. The rest of the `required_*`/`optional_*`/`with_default_*`
+/// surface on the returned `Raw` works identically either way.
+pub fn get_from<'k, S: Source + ?Sized>(source: &S, key: &'k str) -> Raw<'k> {
     Raw {
-        key: key,
-        value: var_os(key),
+        key,
+        value: source.get(key),
+        redact: false,
+    }
+}
+
+/// Masks `value` with one asterisk per character when `redact` is set,
+/// so a key marked via [`Raw::secret`] never leaks its value into an error.
+fn redact_value(value: String, redact: bool) -> String {
+    if redact {
+        "*".repeat(value.chars().count())
+    } else {
+        value
     }
 }
 
@@ -13,6 +44,7 @@ pub fn get(key: &str) -> Raw {
 pub struct Raw<'k> {
     key: &'k str,
     value: Option<OsString>,
+    redact: bool,
 }
 
 impl<'k> Raw<'k> {
@@ -20,16 +52,26 @@ impl<'k> Raw<'k> {
         self.value
     }
 
+    /// Marks this key as holding a secret: any error produced further down
+    /// the pipeline (`Valid`/`Parsed`) stores a masked placeholder instead
+    /// of the real value.
+    pub fn secret(mut self) -> Self {
+        self.redact = true;
+        self
+    }
+
     pub fn required_unchecked(self) -> Result<Valid<'k>, Error> {
         match self.value {
             Some(osstring) => match osstring.into_string() {
                 Ok(string) => Ok(Valid {
                     key: self.key,
                     value: string,
+                    redact: self.redact,
                 }),
                 Err(osstring) => Ok(Valid {
                     key: self.key,
                     value: osstring.to_string_lossy().into_owned(),
+                    redact: self.redact,
                 }),
             },
             None => Err(MissingError {
@@ -45,10 +87,11 @@ impl<'k> Raw<'k> {
                 Ok(string) => Ok(Valid {
                     key: self.key,
                     value: string,
+                    redact: self.redact,
                 }),
                 Err(osstring) => Err(InvalidUnicodeError {
                     key: self.key.to_string(),
-                    value: osstring.to_string_lossy().into_owned(),
+                    value: redact_value(osstring.to_string_lossy().into_owned(), self.redact),
                 }
                 .into()),
             },
@@ -65,10 +108,12 @@ impl<'k> Raw<'k> {
                 Ok(string) => Some(Valid {
                     key: self.key,
                     value: string,
+                    redact: self.redact,
                 }),
                 Err(osstring) => Some(Valid {
                     key: self.key,
                     value: osstring.to_string_lossy().into_owned(),
+                    redact: self.redact,
                 }),
             },
             None => None,
@@ -81,10 +126,11 @@ impl<'k> Raw<'k> {
                 Ok(string) => Ok(Some(Valid {
                     key: self.key,
                     value: string,
+                    redact: self.redact,
                 })),
                 Err(osstring) => Err(InvalidUnicodeError {
                     key: self.key.to_string(),
-                    value: osstring.to_string_lossy().into_owned(),
+                    value: redact_value(osstring.to_string_lossy().into_owned(), self.redact),
                 }
                 .into()),
             },
@@ -98,15 +144,18 @@ impl<'k> Raw<'k> {
                 Ok(string) => Valid {
                     key: self.key,
                     value: string,
+                    redact: self.redact,
                 },
                 Err(osstring) => Valid {
                     key: self.key,
                     value: osstring.to_string_lossy().into_owned(),
+                    redact: self.redact,
                 },
             },
             None => Valid {
                 key: self.key,
                 value: default.into(),
+                redact: self.redact,
             },
         }
     }
@@ -117,15 +166,18 @@ impl<'k> Raw<'k> {
                 Ok(string) => Valid {
                     key: self.key,
                     value: string,
+                    redact: self.redact,
                 },
                 Err(_) => Valid {
                     key: self.key,
                     value: default.into(),
+                    redact: self.redact,
                 },
             },
             None => Valid {
                 key: self.key,
                 value: default.into(),
+                redact: self.redact,
             },
         }
     }
@@ -136,16 +188,18 @@ impl<'k> Raw<'k> {
                 Ok(string) => Ok(Valid {
                     key: self.key,
                     value: string,
+                    redact: self.redact,
                 }),
                 Err(osstring) => Err(InvalidUnicodeError {
                     key: self.key.to_string(),
-                    value: osstring.to_string_lossy().into_owned(),
+                    value: redact_value(osstring.to_string_lossy().into_owned(), self.redact),
                 }
                 .into()),
             },
             None => Ok(Valid {
                 key: self.key,
                 value: default.into(),
+                redact: self.redact,
             }),
         }
     }
@@ -155,6 +209,7 @@ impl<'k> Raw<'k> {
 pub struct Valid<'k> {
     key: &'k str,
     value: String,
+    redact: bool,
 }
 
 impl<'k> Valid<'k> {
@@ -172,18 +227,60 @@ impl<'k> Valid<'k> {
                 inner: parsed,
                 key: self.key,
                 value: self.value,
+                redact: self.redact,
             }),
             Err(err) => Err(ParseError {
                 key: self.key.to_string(),
-                value: self.value,
+                value: redact_value(self.value, self.redact),
                 from: type_name::<&str>(),
                 to: type_name::<T>(),
                 err: err.into(),
+                element: None,
             }
             .into()),
         }
     }
 
+    pub fn then_try_split_fromstr_into<T, C>(self, delim: char) -> Result<Parsed<'k, C>, Error>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: StdError + Send + Sync + 'static,
+        C: FromIterator<T>,
+    {
+        let mut segments: Vec<&str> = self.value.split(delim).map(str::trim).collect();
+        while segments.last().is_some_and(|segment| segment.is_empty()) {
+            segments.pop();
+        }
+        let parsed = segments
+            .into_iter()
+            .enumerate()
+            .map(|(index, segment)| {
+                segment.parse().map_err(|err: <T as FromStr>::Err| {
+                    Box::new(ParseError {
+                        key: self.key.to_string(),
+                        value: redact_value(self.value.clone(), self.redact),
+                        from: type_name::<&str>(),
+                        to: type_name::<T>(),
+                        err: err.into(),
+                        element: Some(ParseErrorElement {
+                            index,
+                            value: redact_value(segment.to_string(), self.redact),
+                        }),
+                    })
+                })
+            })
+            .collect::<Result<C, Box<ParseError>>>();
+        match parsed {
+            Ok(parsed) => Ok(Parsed {
+                inner: parsed,
+                key: self.key,
+                value: self.value,
+                redact: self.redact,
+            }),
+            Err(err) => Err(err.into()),
+        }
+    }
+
     pub fn then_string_into<T>(self) -> Parsed<'k, T>
     where
         String: Into<T>,
@@ -192,6 +289,7 @@ impl<'k> Valid<'k> {
             inner: self.value.clone().into(),
             key: self.key,
             value: self.value,
+            redact: self.redact,
         }
     }
 
@@ -205,13 +303,15 @@ impl<'k> Valid<'k> {
                 inner: parsed,
                 key: self.key,
                 value: self.value,
+                redact: self.redact,
             }),
             Err(err) => Err(ParseError {
                 key: self.key.to_string(),
-                value: self.value,
+                value: redact_value(self.value, self.redact),
                 from: type_name::<String>(),
                 to: type_name::<T>(),
                 err: err.into(),
+                element: None,
             }
             .into()),
         }
@@ -225,6 +325,7 @@ impl<'k> Valid<'k> {
             inner: self.value.as_str().into(),
             key: self.key,
             value: self.value,
+            redact: self.redact,
         }
     }
 
@@ -239,13 +340,15 @@ impl<'k> Valid<'k> {
                 inner: parsed,
                 key: self.key,
                 value: self.value,
+                redact: self.redact,
             }),
             Err(err) => Err(ParseError {
                 key: self.key.to_string(),
-                value: self.value,
+                value: redact_value(self.value, self.redact),
                 from: type_name::<&str>(),
                 to: type_name::<T>(),
-                err: err,
+                err,
+                element: None,
             }
             .into()),
         }
@@ -259,6 +362,7 @@ impl<'k> Valid<'k> {
             inner: f(self.value.clone()),
             key: self.key,
             value: self.value,
+            redact: self.redact,
         }
     }
 
@@ -272,13 +376,15 @@ impl<'k> Valid<'k> {
                 inner: parsed,
                 key: self.key,
                 value: self.value,
+                redact: self.redact,
             }),
             Err(err) => Err(ParseError {
                 key: self.key.to_string(),
-                value: self.value,
+                value: redact_value(self.value, self.redact),
                 from: type_name::<String>(),
                 to: type_name::<T>(),
                 err: err.into(),
+                element: None,
             }
             .into()),
         }
@@ -292,6 +398,7 @@ impl<'k> Valid<'k> {
             inner: f(self.value.as_str()),
             key: self.key,
             value: self.value,
+            redact: self.redact,
         }
     }
 
@@ -305,24 +412,179 @@ impl<'k> Valid<'k> {
                 inner: parsed,
                 key: self.key,
                 value: self.value,
+                redact: self.redact,
             }),
             Err(err) => Err(ParseError {
                 key: self.key.to_string(),
-                value: self.value,
+                value: redact_value(self.value, self.redact),
                 from: type_name::<&str>(),
                 to: type_name::<T>(),
                 err: err.into(),
+                element: None,
+            }
+            .into()),
+        }
+    }
+
+    pub fn then_try_duration_into(self) -> Result<Parsed<'k, Duration>, Error> {
+        match parse_duration(self.value.as_str()) {
+            Ok(duration) => Ok(Parsed {
+                inner: duration,
+                key: self.key,
+                value: self.value,
+                redact: self.redact,
+            }),
+            Err(err) => Err(ParseError {
+                key: self.key.to_string(),
+                value: redact_value(self.value, self.redact),
+                from: type_name::<&str>(),
+                to: type_name::<Duration>(),
+                err: err.into(),
+                element: None,
             }
             .into()),
         }
     }
+
+    pub fn then_try_bytes_into(self) -> Result<Parsed<'k, u64>, Error> {
+        match parse_bytes(self.value.as_str()) {
+            Ok(bytes) => Ok(Parsed {
+                inner: bytes,
+                key: self.key,
+                value: self.value,
+                redact: self.redact,
+            }),
+            Err(err) => Err(ParseError {
+                key: self.key.to_string(),
+                value: redact_value(self.value, self.redact),
+                from: type_name::<&str>(),
+                to: type_name::<u64>(),
+                err: err.into(),
+                element: None,
+            }
+            .into()),
+        }
+    }
+}
+
+/// Parses a leading decimal number off `s`, returning it along with
+/// whatever trails it, e.g. `(1.0, "h30m")` out of `"1h30m"`.
+fn take_number(s: &str) -> Option<(f64, &str)> {
+    let digits_end = s
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(s.len());
+    if digits_end == 0 {
+        return None;
+    }
+    let number: f64 = s[..digits_end].parse().ok()?;
+    Some((number, &s[digits_end..]))
+}
+
+fn parse_duration(s: &str) -> Result<Duration, InvalidDurationError> {
+    const UNITS: &[(&str, f64)] = &[
+        ("ns", 1e-9),
+        ("µs", 1e-6),
+        ("us", 1e-6),
+        ("ms", 1e-3),
+        ("s", 1.0),
+        ("m", 60.0),
+        ("h", 3600.0),
+        ("d", 86400.0),
+    ];
+
+    let s = s.trim();
+    if s.is_empty() {
+        return Err(InvalidDurationError(s.to_string()));
+    }
+
+    let mut total = Duration::ZERO;
+    let mut rest = s;
+    while !rest.is_empty() {
+        let (number, after_number) =
+            take_number(rest).ok_or_else(|| InvalidDurationError(s.to_string()))?;
+        let (unit, seconds_per_unit) = UNITS
+            .iter()
+            .find(|(unit, _)| after_number.starts_with(unit))
+            .ok_or_else(|| InvalidDurationError(s.to_string()))?;
+        let component = Duration::try_from_secs_f64(number * seconds_per_unit)
+            .map_err(|_| InvalidDurationError(s.to_string()))?;
+        total = total
+            .checked_add(component)
+            .ok_or_else(|| InvalidDurationError(s.to_string()))?;
+        rest = &after_number[unit.len()..];
+    }
+    Ok(total)
 }
 
+fn parse_bytes(s: &str) -> Result<u64, InvalidByteSizeError> {
+    let s = s.trim();
+    let (number, rest) = take_number(s).ok_or_else(|| InvalidByteSizeError(s.to_string()))?;
+
+    let mut remainder = rest;
+    let exponent = match remainder.chars().next().map(|c| c.to_ascii_lowercase()) {
+        Some('k') => 1,
+        Some('m') => 2,
+        Some('g') => 3,
+        Some('t') => 4,
+        _ => 0,
+    };
+    if exponent > 0 {
+        remainder = &remainder[1..];
+    }
+
+    let binary = matches!(remainder.chars().next(), Some('i') | Some('I'));
+    if binary {
+        remainder = &remainder[1..];
+    }
+
+    if matches!(remainder.chars().next(), Some('b') | Some('B')) {
+        remainder = &remainder[1..];
+    }
+
+    if !remainder.is_empty() {
+        return Err(InvalidByteSizeError(s.to_string()));
+    }
+
+    let base: f64 = if binary { 1024.0 } else { 1000.0 };
+    Ok((number * base.powi(exponent)).round() as u64)
+}
+
+#[derive(Debug)]
+struct InvalidDurationError(String);
+
+impl fmt::Display for InvalidDurationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?} is not a valid duration (e.g. \"30s\", \"1h30m\")",
+            self.0
+        )
+    }
+}
+
+impl StdError for InvalidDurationError {}
+
+#[derive(Debug)]
+struct InvalidByteSizeError(String);
+
+impl fmt::Display for InvalidByteSizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?} is not a valid byte size (e.g. \"64MiB\", \"1500\")",
+            self.0
+        )
+    }
+}
+
+impl StdError for InvalidByteSizeError {}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Parsed<'k, P> {
     key: &'k str,
     value: String,
     inner: P,
+    redact: bool,
 }
 
 impl<'k, P> Parsed<'k, P> {
@@ -338,6 +600,7 @@ impl<'k, P> Parsed<'k, P> {
             inner: self.inner.into(),
             key: self.key,
             value: self.value,
+            redact: self.redact,
         }
     }
 
@@ -351,13 +614,15 @@ impl<'k, P> Parsed<'k, P> {
                 inner: parsed,
                 key: self.key,
                 value: self.value,
+                redact: self.redact,
             }),
             Err(err) => Err(ParseError {
                 key: self.key.to_string(),
-                value: self.value,
+                value: redact_value(self.value, self.redact),
                 from: type_name::<P>(),
                 to: type_name::<T>(),
                 err: err.into(),
+                element: None,
             }
             .into()),
         }
@@ -371,6 +636,7 @@ impl<'k, P> Parsed<'k, P> {
             inner: f(self.inner),
             key: self.key,
             value: self.value,
+            redact: self.redact,
         }
     }
 
@@ -384,15 +650,247 @@ impl<'k, P> Parsed<'k, P> {
                 inner: parsed,
                 key: self.key,
                 value: self.value,
+                redact: self.redact,
             }),
             Err(err) => Err(ParseError {
                 key: self.key.to_string(),
-                value: self.value,
+                value: redact_value(self.value, self.redact),
                 from: type_name::<P>(),
                 to: type_name::<T>(),
                 err: err.into(),
+                element: None,
             }
             .into()),
         }
     }
+
+    pub fn then_in_range<R>(self, range: R) -> Result<Parsed<'k, P>, Error>
+    where
+        P: PartialOrd + Display,
+        R: RangeBounds<P>,
+    {
+        if range.contains(&self.inner) {
+            Ok(self)
+        } else {
+            let (min, min_inclusive) = match range.start_bound() {
+                Bound::Included(start) => (Some(start.to_string()), true),
+                Bound::Excluded(start) => (Some(start.to_string()), false),
+                Bound::Unbounded => (None, false),
+            };
+            let (max, max_inclusive) = match range.end_bound() {
+                Bound::Included(end) => (Some(end.to_string()), true),
+                Bound::Excluded(end) => (Some(end.to_string()), false),
+                Bound::Unbounded => (None, false),
+            };
+            Err(OutOfRangeError {
+                key: self.key.to_string(),
+                value: redact_value(self.inner.to_string(), self.redact),
+                min,
+                min_inclusive,
+                max,
+                max_inclusive,
+            }
+            .into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_duration_round_trips() {
+        assert_eq!(parse_duration("30s").unwrap(), Duration::from_secs(30));
+        assert_eq!(
+            parse_duration("1h30m").unwrap(),
+            Duration::from_secs(90 * 60)
+        );
+        assert_eq!(parse_duration("5ms").unwrap(), Duration::from_millis(5));
+        assert_eq!(
+            parse_duration("1h30m").unwrap(),
+            parse_duration("90m").unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_duration_rejects_trailing_bare_number() {
+        assert!(parse_duration("30").is_err());
+    }
+
+    #[test]
+    fn parse_duration_reports_an_error_instead_of_panicking_on_overflow() {
+        assert!(parse_duration("99999999999999999999s").is_err());
+    }
+
+    #[test]
+    fn parse_bytes_round_trips() {
+        assert_eq!(parse_bytes("1500").unwrap(), 1500);
+        assert_eq!(parse_bytes("64MiB").unwrap(), 64 * 1024 * 1024);
+        assert_eq!(parse_bytes("1k").unwrap(), 1000);
+        assert_eq!(parse_bytes("5B").unwrap(), 5);
+    }
+
+    #[test]
+    fn then_try_split_fromstr_into_parses_each_trimmed_segment() {
+        let valid = Valid {
+            key: "PORTS",
+            value: "80, 443,8080 ,".to_string(),
+            redact: false,
+        };
+        let parsed = valid
+            .then_try_split_fromstr_into::<u16, Vec<u16>>(',')
+            .unwrap();
+        assert_eq!(parsed.into_inner(), vec![80, 443, 8080]);
+    }
+
+    #[test]
+    fn then_try_split_fromstr_into_reports_the_failing_element() {
+        let valid = Valid {
+            key: "PORTS",
+            value: "80, nope, 8080".to_string(),
+            redact: false,
+        };
+        let err = valid
+            .then_try_split_fromstr_into::<u16, Vec<u16>>(',')
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("element 1 (\"nope\")"),
+            "unexpected error message: {err}"
+        );
+    }
+
+    #[test]
+    fn then_in_range_reports_inclusive_and_exclusive_bounds_distinctly() {
+        let parsed = Parsed {
+            key: "PORT",
+            value: "9000".to_string(),
+            inner: 9000u32,
+            redact: false,
+        };
+        let err = parsed.then_in_range(3000..9000).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "environment variable `PORT` (\"9000\") is out of range: expected a value in [3000, 9000)"
+        );
+
+        let parsed = Parsed {
+            key: "PORT",
+            value: "9000".to_string(),
+            inner: 9000u32,
+            redact: false,
+        };
+        assert!(parsed.then_in_range(3000..=9000).is_ok());
+    }
+
+    #[test]
+    fn secret_masks_invalid_unicode_but_unredacted_shows_the_real_value() {
+        use std::os::unix::ffi::OsStringExt;
+
+        let invalid = OsString::from_vec(vec![0x66, 0x6f, 0x80, 0x6f]);
+
+        let err = Raw {
+            key: "TOKEN",
+            value: Some(invalid.clone()),
+            redact: true,
+        }
+        .required_checked()
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "environment variable `TOKEN` is not valid unicode: \"****\""
+        );
+
+        let err = Raw {
+            key: "TOKEN",
+            value: Some(invalid),
+            redact: false,
+        }
+        .required_checked()
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "environment variable `TOKEN` is not valid unicode: \"fo\u{fffd}o\""
+        );
+    }
+
+    #[test]
+    fn secret_masks_then_try_fromstr_into_but_unredacted_shows_the_real_value() {
+        let err = Valid {
+            key: "PORT",
+            value: "nope".to_string(),
+            redact: true,
+        }
+        .then_try_fromstr_into::<u16>()
+        .unwrap_err();
+        assert!(
+            err.to_string().contains("(\"****\")"),
+            "unexpected error message: {err}"
+        );
+
+        let err = Valid {
+            key: "PORT",
+            value: "nope".to_string(),
+            redact: false,
+        }
+        .then_try_fromstr_into::<u16>()
+        .unwrap_err();
+        assert!(
+            err.to_string().contains("(\"nope\")"),
+            "unexpected error message: {err}"
+        );
+    }
+
+    #[test]
+    fn secret_masks_then_try_split_fromstr_into_including_the_failing_element() {
+        let err = Valid {
+            key: "PORTS",
+            value: "80, nope, 8080".to_string(),
+            redact: true,
+        }
+        .then_try_split_fromstr_into::<u16, Vec<u16>>(',')
+        .unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("(\"**************\")"), "{message}");
+        assert!(message.contains("element 1 (\"****\")"), "{message}");
+
+        let err = Valid {
+            key: "PORTS",
+            value: "80, nope, 8080".to_string(),
+            redact: false,
+        }
+        .then_try_split_fromstr_into::<u16, Vec<u16>>(',')
+        .unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("element 1 (\"nope\")"), "{message}");
+    }
+
+    #[test]
+    fn secret_masks_then_in_range_but_unredacted_shows_the_real_value() {
+        let err = Parsed {
+            key: "PORT",
+            value: "9000".to_string(),
+            inner: 9000u32,
+            redact: true,
+        }
+        .then_in_range(3000..9000)
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "environment variable `PORT` (\"****\") is out of range: expected a value in [3000, 9000)"
+        );
+
+        let err = Parsed {
+            key: "PORT",
+            value: "9000".to_string(),
+            inner: 9000u32,
+            redact: false,
+        }
+        .then_in_range(3000..9000)
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "environment variable `PORT` (\"9000\") is out of range: expected a value in [3000, 9000)"
+        );
+    }
 }
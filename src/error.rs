@@ -0,0 +1,226 @@
+use std::{error::Error as StdError, fmt};
+
+#[derive(Debug)]
+pub enum Error {
+    Missing(MissingError),
+    InvalidUnicode(InvalidUnicodeError),
+    Parse(Box<ParseError>),
+    OutOfRange(OutOfRangeError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Missing(err) => err.fmt(f),
+            Error::InvalidUnicode(err) => err.fmt(f),
+            Error::Parse(err) => err.fmt(f),
+            Error::OutOfRange(err) => err.fmt(f),
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Error::Missing(err) => err.source(),
+            Error::InvalidUnicode(err) => err.source(),
+            Error::Parse(err) => err.source(),
+            Error::OutOfRange(err) => err.source(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct MissingError {
+    pub key: String,
+}
+
+impl fmt::Display for MissingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "missing required environment variable `{}`", self.key)
+    }
+}
+
+impl StdError for MissingError {}
+
+impl From<MissingError> for Error {
+    fn from(err: MissingError) -> Self {
+        Error::Missing(err)
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidUnicodeError {
+    pub key: String,
+    pub value: String,
+}
+
+impl fmt::Display for InvalidUnicodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "environment variable `{}` is not valid unicode: {:?}",
+            self.key, self.value
+        )
+    }
+}
+
+impl StdError for InvalidUnicodeError {}
+
+impl From<InvalidUnicodeError> for Error {
+    fn from(err: InvalidUnicodeError) -> Self {
+        Error::InvalidUnicode(err)
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseError {
+    pub key: String,
+    pub value: String,
+    pub from: &'static str,
+    pub to: &'static str,
+    pub err: Box<dyn StdError + Send + Sync>,
+    pub element: Option<ParseErrorElement>,
+}
+
+/// Identifies which element of a delimited collection failed to parse.
+#[derive(Debug)]
+pub struct ParseErrorElement {
+    pub index: usize,
+    pub value: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.element {
+            Some(element) => write!(
+                f,
+                "failed to parse environment variable `{}` ({:?}) from `{}` into `{}`: \
+                 element {} ({:?}): {}",
+                self.key, self.value, self.from, self.to, element.index, element.value, self.err
+            ),
+            None => write!(
+                f,
+                "failed to parse environment variable `{}` ({:?}) from `{}` into `{}`: {}",
+                self.key, self.value, self.from, self.to, self.err
+            ),
+        }
+    }
+}
+
+impl StdError for ParseError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(self.err.as_ref())
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Error::Parse(Box::new(err))
+    }
+}
+
+impl From<Box<ParseError>> for Error {
+    fn from(err: Box<ParseError>) -> Self {
+        Error::Parse(err)
+    }
+}
+
+#[derive(Debug)]
+pub struct OutOfRangeError {
+    pub key: String,
+    pub value: String,
+    pub min: Option<String>,
+    pub min_inclusive: bool,
+    pub max: Option<String>,
+    pub max_inclusive: bool,
+}
+
+impl fmt::Display for OutOfRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.min, &self.max) {
+            (Some(min), Some(max)) => write!(
+                f,
+                "environment variable `{}` ({:?}) is out of range: expected a value in {}{}, {}{}",
+                self.key,
+                self.value,
+                if self.min_inclusive { '[' } else { '(' },
+                min,
+                max,
+                if self.max_inclusive { ']' } else { ')' },
+            ),
+            (Some(min), None) => write!(
+                f,
+                "environment variable `{}` ({:?}) is out of range: expected a value {} {}",
+                self.key,
+                self.value,
+                if self.min_inclusive { ">=" } else { ">" },
+                min
+            ),
+            (None, Some(max)) => write!(
+                f,
+                "environment variable `{}` ({:?}) is out of range: expected a value {} {}",
+                self.key,
+                self.value,
+                if self.max_inclusive { "<=" } else { "<" },
+                max
+            ),
+            (None, None) => write!(
+                f,
+                "environment variable `{}` ({:?}) is out of range",
+                self.key, self.value
+            ),
+        }
+    }
+}
+
+impl StdError for OutOfRangeError {}
+
+impl From<OutOfRangeError> for Error {
+    fn from(err: OutOfRangeError) -> Self {
+        Error::OutOfRange(err)
+    }
+}
+
+impl Error {
+    /// The key of the environment variable that caused this error.
+    pub fn key(&self) -> &str {
+        match self {
+            Error::Missing(err) => &err.key,
+            Error::InvalidUnicode(err) => &err.key,
+            Error::Parse(err) => &err.key,
+            Error::OutOfRange(err) => &err.key,
+        }
+    }
+}
+
+/// An aggregate of every [`Error`] collected while validating a batch of
+/// environment variables, e.g. via `EnvBatch::finish`.
+#[derive(Debug)]
+pub struct Errors(pub Vec<Error>);
+
+impl Errors {
+    pub fn errors(&self) -> &[Error] {
+        &self.0
+    }
+
+    pub fn into_errors(self) -> Vec<Error> {
+        self.0
+    }
+}
+
+impl fmt::Display for Errors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} environment variable(s) failed validation:",
+            self.0.len()
+        )?;
+        for err in &self.0 {
+            writeln!(f, "  - {}: {}", err.key(), err)?;
+        }
+        Ok(())
+    }
+}
+
+impl StdError for Errors {}
@@ -0,0 +1,13 @@
+mod batch;
+mod env_util;
+mod error;
+mod source;
+
+pub use batch::EnvBatch;
+pub use env_util::{get, get_from, Parsed, Raw, Valid};
+pub use env_util_derive::FromEnv;
+pub use error::{
+    Error, Errors, InvalidUnicodeError, MissingError, OutOfRangeError, ParseError,
+    ParseErrorElement,
+};
+pub use source::{Chain, DotEnv, Env, Source};
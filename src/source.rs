@@ -0,0 +1,208 @@
+use std::{collections::HashMap, ffi::OsString, fs, path::PathBuf, sync::OnceLock};
+
+/// A place `get_from` can read a key's raw value from, in place of the live
+/// process environment.
+pub trait Source {
+    fn get(&self, key: &str) -> Option<OsString>;
+}
+
+/// The live process environment, as read by [`crate::env_util::get`].
+pub struct Env;
+
+impl Source for Env {
+    fn get(&self, key: &str) -> Option<OsString> {
+        std::env::var_os(key)
+    }
+}
+
+/// A `.env`-style file (`KEY=VALUE`, `#` comments, optional `export ` prefix,
+/// quoted values with escape handling), parsed and cached on first lookup.
+pub struct DotEnv {
+    path: PathBuf,
+    cache: OnceLock<HashMap<String, OsString>>,
+}
+
+impl DotEnv {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            cache: OnceLock::new(),
+        }
+    }
+
+    fn entries(&self) -> &HashMap<String, OsString> {
+        self.cache.get_or_init(|| {
+            fs::read_to_string(&self.path)
+                .map(|contents| parse_dotenv(&contents))
+                .unwrap_or_default()
+        })
+    }
+}
+
+impl Source for DotEnv {
+    fn get(&self, key: &str) -> Option<OsString> {
+        self.entries().get(key).cloned()
+    }
+}
+
+fn parse_dotenv(contents: &str) -> HashMap<String, OsString> {
+    let mut entries = HashMap::new();
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line).trim_start();
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        entries.insert(
+            key.trim().to_string(),
+            OsString::from(unquote(value.trim())),
+        );
+    }
+    entries
+}
+
+fn unquote(value: &str) -> String {
+    if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+        unescape_double_quoted(&value[1..value.len() - 1])
+    } else if value.len() >= 2 && value.starts_with('\'') && value.ends_with('\'') {
+        value[1..value.len() - 1].to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+fn unescape_double_quoted(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            Some('"') => out.push('"'),
+            Some('\\') => out.push('\\'),
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Tries each inner [`Source`] in order, returning the first hit.
+#[derive(Default)]
+pub struct Chain {
+    sources: Vec<Box<dyn Source>>,
+}
+
+impl Chain {
+    pub fn new() -> Self {
+        Self {
+            sources: Vec::new(),
+        }
+    }
+
+    pub fn push(mut self, source: impl Source + 'static) -> Self {
+        self.sources.push(Box::new(source));
+        self
+    }
+}
+
+impl Source for Chain {
+    fn get(&self, key: &str) -> Option<OsString> {
+        self.sources.iter().find_map(|source| source.get(key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_plain_and_quoted_values() {
+        let entries = parse_dotenv(
+            "\
+# a comment
+export FOO=bar
+BAZ = \"quo ted\"
+SINGLE='raw \\n stays'
+EMPTY=
+
+QUX=unquoted # not stripped
+",
+        );
+        assert_eq!(entries.get("FOO"), Some(&OsString::from("bar")));
+        assert_eq!(entries.get("BAZ"), Some(&OsString::from("quo ted")));
+        assert_eq!(
+            entries.get("SINGLE"),
+            Some(&OsString::from("raw \\n stays"))
+        );
+        assert_eq!(entries.get("EMPTY"), Some(&OsString::from("")));
+        assert_eq!(
+            entries.get("QUX"),
+            Some(&OsString::from("unquoted # not stripped"))
+        );
+    }
+
+    #[test]
+    fn unescapes_double_quoted_sequences() {
+        assert_eq!(unquote("\"a\\nb\\tc\\\"d\\\\e\""), "a\nb\tc\"d\\e");
+    }
+
+    #[test]
+    fn chain_returns_first_hit() {
+        let mut first = HashMap::new();
+        first.insert("A".to_string(), OsString::from("from-first"));
+        struct Map(HashMap<String, OsString>);
+        impl Source for Map {
+            fn get(&self, key: &str) -> Option<OsString> {
+                self.0.get(key).cloned()
+            }
+        }
+        let mut second = HashMap::new();
+        second.insert("A".to_string(), OsString::from("from-second"));
+        second.insert("B".to_string(), OsString::from("only-in-second"));
+
+        let chain = Chain::new().push(Map(first)).push(Map(second));
+        assert_eq!(chain.get("A"), Some(OsString::from("from-first")));
+        assert_eq!(chain.get("B"), Some(OsString::from("only-in-second")));
+        assert_eq!(chain.get("C"), None);
+    }
+
+    #[test]
+    fn dot_env_reads_and_caches_a_real_file() {
+        let path =
+            std::env::temp_dir().join(format!("env_util_dotenv_test_{}.env", std::process::id()));
+        fs::write(&path, "FOO=bar\n").unwrap();
+
+        let dot_env = DotEnv::new(&path);
+        assert_eq!(dot_env.get("FOO"), Some(OsString::from("bar")));
+
+        // The cache is already populated, so rewriting the file on disk
+        // must not change what a second lookup sees.
+        fs::write(&path, "FOO=changed\n").unwrap();
+        assert_eq!(dot_env.get("FOO"), Some(OsString::from("bar")));
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn dot_env_falls_back_to_an_empty_map_when_the_file_is_missing() {
+        let path = std::env::temp_dir().join(format!(
+            "env_util_dotenv_test_missing_{}.env",
+            std::process::id()
+        ));
+
+        let dot_env = DotEnv::new(&path);
+        assert_eq!(dot_env.get("FOO"), None);
+    }
+}
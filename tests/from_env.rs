@@ -0,0 +1,57 @@
+use env_util::FromEnv;
+
+#[derive(FromEnv, Debug, PartialEq)]
+#[env(prefix = "ENV_UTIL_DERIVE_TEST_OK_")]
+struct OkConfig {
+    #[env(key = "ENV_UTIL_DERIVE_TEST_OK_DATABASE_URL")]
+    database_url: String,
+    #[env(default = "8080")]
+    port: u16,
+    #[env(optional)]
+    feature_flag: Option<String>,
+}
+
+#[derive(FromEnv, Debug, PartialEq)]
+#[env(prefix = "ENV_UTIL_DERIVE_TEST_ERR_")]
+struct ErrConfig {
+    #[env(key = "ENV_UTIL_DERIVE_TEST_ERR_DATABASE_URL")]
+    database_url: String,
+    #[env(default = "8080")]
+    port: u16,
+    #[env(optional)]
+    feature_flag: Option<String>,
+}
+
+#[test]
+fn from_env_populates_keyed_defaulted_and_optional_fields() {
+    std::env::set_var(
+        "ENV_UTIL_DERIVE_TEST_OK_DATABASE_URL",
+        "postgres://localhost/app",
+    );
+    std::env::remove_var("ENV_UTIL_DERIVE_TEST_OK_PORT");
+    std::env::remove_var("ENV_UTIL_DERIVE_TEST_OK_FEATURE_FLAG");
+
+    let config = OkConfig::from_env().unwrap();
+    assert_eq!(
+        config,
+        OkConfig {
+            database_url: "postgres://localhost/app".to_string(),
+            port: 8080,
+            feature_flag: None,
+        }
+    );
+
+    std::env::remove_var("ENV_UTIL_DERIVE_TEST_OK_DATABASE_URL");
+}
+
+#[test]
+fn from_env_collects_every_field_error() {
+    std::env::remove_var("ENV_UTIL_DERIVE_TEST_ERR_DATABASE_URL");
+    std::env::set_var("ENV_UTIL_DERIVE_TEST_ERR_PORT", "not-a-port");
+    std::env::remove_var("ENV_UTIL_DERIVE_TEST_ERR_FEATURE_FLAG");
+
+    let errors = ErrConfig::from_env().unwrap_err();
+    assert_eq!(errors.errors().len(), 2);
+
+    std::env::remove_var("ENV_UTIL_DERIVE_TEST_ERR_PORT");
+}